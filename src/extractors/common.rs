@@ -0,0 +1,201 @@
+use filetime::{set_file_mtime, FileTime};
+use std::ffi::CString;
+use std::fs;
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+/// Result of an extraction attempt
+#[derive(Debug, Default, Clone)]
+pub struct ExtractionResult {
+    /// Whether extraction succeeded
+    pub success: bool,
+    /// Number of input bytes consumed by the extracted data, if known
+    pub size: Option<usize>,
+    /// Number of entries whose data failed integrity verification
+    pub corrupt_entries: usize,
+}
+
+/// Describes how to invoke an extractor: an internal Rust function or an external command
+#[derive(Debug, Clone, Default)]
+pub enum ExtractorType {
+    #[default]
+    None,
+    Internal(fn(&[u8], usize, Option<&str>) -> ExtractionResult),
+    External(String),
+}
+
+/// An extractor definition: what utility to invoke to carve out an embedded file
+#[derive(Debug, Clone, Default)]
+pub struct Extractor {
+    pub utility: ExtractorType,
+}
+
+/// Confines extracted files to a given output directory, providing the filesystem primitives
+/// extractors need to reconstruct an archive's contents (directories, regular files, symlinks,
+/// device nodes)
+#[derive(Debug, Clone)]
+pub struct Chroot {
+    output_directory: Option<PathBuf>,
+}
+
+impl Chroot {
+    pub fn new(output_directory: Option<&str>) -> Self {
+        Chroot {
+            output_directory: output_directory.map(PathBuf::from),
+        }
+    }
+
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        self.output_directory.as_ref().map(|dir| dir.join(path))
+    }
+
+    pub fn create_directory(&self, path: &str) -> bool {
+        match self.resolve(path) {
+            Some(full_path) => fs::create_dir_all(full_path).is_ok(),
+            None => false,
+        }
+    }
+
+    pub fn carve_file(&self, path: &str, file_data: &[u8], offset: usize, size: usize) -> bool {
+        let Some(full_path) = self.resolve(path) else {
+            return false;
+        };
+        let Some(parent) = full_path.parent() else {
+            return false;
+        };
+        let Some(carved_data) = file_data.get(offset..offset + size) else {
+            return false;
+        };
+
+        fs::create_dir_all(parent).is_ok() && fs::write(full_path, carved_data).is_ok()
+    }
+
+    pub fn append_to_file(&self, path: &str, data: &[u8]) -> bool {
+        let Some(full_path) = self.resolve(path) else {
+            return false;
+        };
+        let Some(parent) = full_path.parent() else {
+            return false;
+        };
+
+        if fs::create_dir_all(parent).is_err() {
+            return false;
+        }
+
+        match fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(full_path)
+        {
+            Ok(mut file) => file.write_all(data).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn create_symlink(&self, path: &str, target: String) -> bool {
+        let Some(full_path) = self.resolve(path) else {
+            return false;
+        };
+        let Some(parent) = full_path.parent() else {
+            return false;
+        };
+
+        fs::create_dir_all(parent).is_ok() && std::os::unix::fs::symlink(target, full_path).is_ok()
+    }
+
+    /// Materializes `path` as a hard link to the already-extracted `source` path; returns false
+    /// if the platform or filesystem rejects the link (e.g. a cross-device boundary). No bytes
+    /// are copied here, so any copy fallback is up to the caller
+    pub fn create_hardlink(&self, path: &str, source: &str) -> bool {
+        let (Some(full_path), Some(source_path)) = (self.resolve(path), self.resolve(source))
+        else {
+            return false;
+        };
+        let Some(parent) = full_path.parent() else {
+            return false;
+        };
+
+        fs::create_dir_all(parent).is_ok() && fs::hard_link(source_path, full_path).is_ok()
+    }
+
+    pub fn create_fifo(&self, path: &str) -> bool {
+        self.mknod(path, libc::S_IFIFO, 0)
+    }
+
+    pub fn create_socket(&self, path: &str) -> bool {
+        self.mknod(path, libc::S_IFSOCK, 0)
+    }
+
+    pub fn create_block_device(&self, path: &str, major: usize, minor: usize) -> bool {
+        self.mknod(path, libc::S_IFBLK, Self::makedev(major, minor))
+    }
+
+    pub fn create_character_device(&self, path: &str, major: usize, minor: usize) -> bool {
+        self.mknod(path, libc::S_IFCHR, Self::makedev(major, minor))
+    }
+
+    pub fn make_executable(&self, path: &str) -> bool {
+        let Some(full_path) = self.resolve(path) else {
+            return false;
+        };
+
+        match fs::metadata(&full_path) {
+            Ok(metadata) => {
+                let mut permissions = metadata.permissions();
+                permissions.set_mode(permissions.mode() | 0o111);
+                fs::set_permissions(&full_path, permissions).is_ok()
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Applies the original archive entry's uid/gid to an already-extracted path; best-effort,
+    /// since a non-privileged process cannot `chown` to an arbitrary uid, so failure is ignored
+    pub fn set_ownership(&self, path: &str, uid: usize, gid: usize) {
+        let Some(full_path) = self.resolve(path) else {
+            return;
+        };
+        let Ok(c_path) = CString::new(full_path.as_os_str().as_bytes()) else {
+            return;
+        };
+
+        // SAFETY: c_path is a NUL-terminated path string owned for the duration of this call
+        unsafe {
+            libc::chown(c_path.as_ptr(), uid as libc::uid_t, gid as libc::gid_t);
+        }
+    }
+
+    /// Applies the original archive entry's mtime to an already-extracted path; best-effort
+    pub fn set_mtime(&self, path: &str, mtime: u64) {
+        let Some(full_path) = self.resolve(path) else {
+            return;
+        };
+
+        let _ = set_file_mtime(full_path, FileTime::from_unix_time(mtime as i64, 0));
+    }
+
+    fn makedev(major: usize, minor: usize) -> libc::dev_t {
+        // SAFETY: makedev() is a pure computation over its two integer arguments
+        unsafe { libc::makedev(major as u32, minor as u32) }
+    }
+
+    fn mknod(&self, path: &str, node_type: libc::mode_t, dev: libc::dev_t) -> bool {
+        let Some(full_path) = self.resolve(path) else {
+            return false;
+        };
+        let Some(parent) = full_path.parent() else {
+            return false;
+        };
+        if fs::create_dir_all(parent).is_err() {
+            return false;
+        }
+        let Ok(c_path) = CString::new(full_path.as_os_str().as_bytes()) else {
+            return false;
+        };
+
+        // SAFETY: c_path is a NUL-terminated path string owned for the duration of this call
+        unsafe { libc::mknod(c_path.as_ptr(), node_type | 0o644, dev) == 0 }
+    }
+}
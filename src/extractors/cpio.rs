@@ -1,10 +1,18 @@
 use crate::common::is_offset_safe;
 use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
-use crate::structures::cpio::{parse_cpio_entry_header, is_executable, CPIOFileType};
+use crate::structures::cpio::{
+    is_executable, parse_cpio_entry_header, CPIOEntryHeader, CPIOFileType, CPIOVariant,
+};
 use log::warn;
+use serde::Serialize;
+use std::collections::HashMap;
 
 const EOF_MARKER: &str = "TRAILER!!!";
 
+/// Name of the JSON manifest written alongside extracted entries, describing the original
+/// archive's metadata (ownership, timestamps, device numbers) for downstream tooling
+const MANIFEST_FILE_NAME: &str = "cpio_manifest.json";
+
 pub fn cpio_extractor() -> Extractor {
     Extractor {
         utility: ExtractorType::Internal(extract_cpio),
@@ -25,6 +33,7 @@ pub fn extract_cpio(
     let mut next_offset = offset;
     let mut previous_offset = None;
     let mut total_size: usize = 0;
+    let mut corrupt_entries: usize = 0;
     let mut entries: Vec<CPIOEntry> = vec![];
 
     while is_offset_safe(available_data, next_offset, previous_offset) {
@@ -44,16 +53,28 @@ pub fn extract_cpio(
                         }
 
                         let data_offset = next_offset + header.header_size;
-                        let data_size = header.data_size;
+
+                        if !verify_cpio_checksum(file_data, data_offset, &header) {
+                            corrupt_entries += 1;
+                            warn!(
+                                "CPIO entry '{}' failed checksum verification",
+                                header.file_name
+                            );
+                        }
 
                         entries.push(CPIOEntry {
                             name: header.file_name.clone(),
                             file_type: header.file_type,
                             mode: header.mode,
                             data_offset,
-                            data_size,
-                            dev_major: header.dev_major,
-                            dev_minor: header.dev_minor,
+                            file_size: header.file_size,
+                            uid: header.uid,
+                            gid: header.gid,
+                            mtime: header.mtime,
+                            rdev_major: header.rdev_major,
+                            rdev_minor: header.rdev_minor,
+                            ino: header.ino,
+                            nlink: header.nlink,
                         });
 
                         previous_offset = Some(next_offset);
@@ -64,16 +85,62 @@ pub fn extract_cpio(
         }
     }
 
+    result.corrupt_entries = corrupt_entries;
+
     if result.success && output_directory.is_some() {
         let chroot = Chroot::new(output_directory);
         let mut extracted_count: usize = 0;
 
-        for entry in &entries {
-            if extract_cpio_entry(file_data, entry, &chroot) {
+        write_cpio_manifest(&entries, &chroot);
+
+        // A hardlinked inode's file data is only carried by whichever entry satisfies its
+        // nlink count last; earlier entries sharing that inode have a zero file_size.
+        let mut data_carrier_idx: HashMap<usize, usize> = HashMap::new();
+        for (idx, entry) in entries.iter().enumerate() {
+            if entry.is_hardlinked() && entry.file_size > 0 {
+                data_carrier_idx.insert(entry.ino, idx);
+            }
+        }
+
+        // Directories, symlinks, hardlinks and device nodes go through a deterministic serial
+        // pass first so that path dependencies (e.g. a parent directory) are satisfied. Plain
+        // regular files have no such dependency and are carved in a separate, optionally
+        // parallel, pass below.
+        let mut parallel_entries: Vec<&CPIOEntry> = vec![];
+
+        // Link entries that carry no data are handled after everything else, so that their
+        // data carrier already exists on disk when the hard link is made
+        let mut serial_order: Vec<(usize, &CPIOEntry)> = entries.iter().enumerate().collect();
+        serial_order.sort_by_key(|&(idx, entry)| {
+            entry.is_hardlinked() && data_carrier_idx.get(&entry.ino) != Some(&idx)
+        });
+
+        for (idx, entry) in serial_order {
+            if entry.file_type == CPIOFileType::Regular && !entry.is_hardlinked() {
+                parallel_entries.push(entry);
+                continue;
+            }
+
+            let is_data_carrier = data_carrier_idx.get(&entry.ino) == Some(&idx);
+
+            let extracted = if !entry.is_hardlinked() || is_data_carrier {
+                extract_cpio_entry(file_data, entry, &chroot)
+            } else if let Some(&carrier_idx) = data_carrier_idx.get(&entry.ino) {
+                extract_cpio_hardlink(file_data, &entries[carrier_idx], entry, &chroot)
+            } else {
+                warn!("No file data found for hardlinked CPIO entry: {}", entry.name);
+                false
+            };
+
+            if extracted {
+                chroot.set_ownership(&entry.name, entry.uid, entry.gid);
+                chroot.set_mtime(&entry.name, entry.mtime);
                 extracted_count += 1;
             }
         }
 
+        extracted_count += carve_regular_entries(file_data, &parallel_entries, &chroot);
+
         if extracted_count == 0 {
             result.success = false;
         }
@@ -88,9 +155,21 @@ struct CPIOEntry {
     file_type: CPIOFileType,
     mode: usize,
     data_offset: usize,
-    data_size: usize,
-    dev_major: usize,
-    dev_minor: usize,
+    file_size: usize,
+    uid: usize,
+    gid: usize,
+    mtime: u64,
+    rdev_major: usize,
+    rdev_minor: usize,
+    ino: usize,
+    nlink: usize,
+}
+
+impl CPIOEntry {
+    /// True if this entry is one of several links to the same on-disk file
+    fn is_hardlinked(&self) -> bool {
+        self.file_type == CPIOFileType::Regular && self.ino != 0 && self.nlink > 1
+    }
 }
 
 fn extract_cpio_entry(file_data: &[u8], entry: &CPIOEntry, chroot: &Chroot) -> bool {
@@ -99,13 +178,11 @@ fn extract_cpio_entry(file_data: &[u8], entry: &CPIOEntry, chroot: &Chroot) -> b
     let extraction_success = match entry.file_type {
         CPIOFileType::Directory => chroot.create_directory(file_path),
         CPIOFileType::Regular => {
-            let actual_size = entry.data_size - calculate_padding(entry.data_size);
-            chroot.carve_file(file_path, file_data, entry.data_offset, actual_size)
+            chroot.carve_file(file_path, file_data, entry.data_offset, entry.file_size)
         }
         CPIOFileType::Symlink => {
-            let actual_size = entry.data_size - calculate_padding(entry.data_size);
             if let Some(target_bytes) =
-                file_data.get(entry.data_offset..entry.data_offset + actual_size)
+                file_data.get(entry.data_offset..entry.data_offset + entry.file_size)
             {
                 let target_bytes_clean: Vec<u8> = target_bytes
                     .iter()
@@ -125,10 +202,10 @@ fn extract_cpio_entry(file_data: &[u8], entry: &CPIOEntry, chroot: &Chroot) -> b
         CPIOFileType::Fifo => chroot.create_fifo(file_path),
         CPIOFileType::Socket => chroot.create_socket(file_path),
         CPIOFileType::BlockDevice => {
-            chroot.create_block_device(file_path, entry.dev_major, entry.dev_minor)
+            chroot.create_block_device(file_path, entry.rdev_major, entry.rdev_minor)
         }
         CPIOFileType::CharDevice => {
-            chroot.create_character_device(file_path, entry.dev_major, entry.dev_minor)
+            chroot.create_character_device(file_path, entry.rdev_major, entry.rdev_minor)
         }
         CPIOFileType::Unknown => {
             warn!("Unknown file type for {}", file_path);
@@ -147,11 +224,314 @@ fn extract_cpio_entry(file_data: &[u8], entry: &CPIOEntry, chroot: &Chroot) -> b
     extraction_success
 }
 
-fn calculate_padding(size: usize) -> usize {
-    let modulus = size % 4;
-    if modulus == 0 {
-        0
+/// Materializes a same-inode `entry` as a hardlink to the already-extracted `source` path,
+/// falling back to copying the carved bytes if the platform rejects the link
+fn extract_cpio_hardlink(
+    file_data: &[u8],
+    source: &CPIOEntry,
+    entry: &CPIOEntry,
+    chroot: &Chroot,
+) -> bool {
+    let file_path = &entry.name;
+
+    let extraction_success = chroot.create_hardlink(file_path, &source.name)
+        || chroot.carve_file(file_path, file_data, source.data_offset, source.file_size);
+
+    if extraction_success {
+        if is_executable(entry.mode) {
+            chroot.make_executable(file_path);
+        }
     } else {
-        4 - modulus
+        warn!("Failed to extract hardlinked CPIO entry: {}", file_path);
+    }
+
+    extraction_success
+}
+
+/// Carves every plain (non-hardlinked) regular file entry's content, applying ownership and
+/// mtime metadata on success. Entries have no path dependency on one another, so when the
+/// `rayon` feature is enabled they are carved concurrently; returns the number extracted.
+#[cfg(feature = "rayon")]
+fn carve_regular_entries(file_data: &[u8], entries: &[&CPIOEntry], chroot: &Chroot) -> usize {
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let extracted_count = AtomicUsize::new(0);
+
+    entries.par_iter().for_each(|entry| {
+        if extract_cpio_entry(file_data, entry, chroot) {
+            chroot.set_ownership(&entry.name, entry.uid, entry.gid);
+            chroot.set_mtime(&entry.name, entry.mtime);
+            extracted_count.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+
+    extracted_count.load(Ordering::Relaxed)
+}
+
+/// Serial fallback of [`carve_regular_entries`] for builds without the `rayon` feature
+#[cfg(not(feature = "rayon"))]
+fn carve_regular_entries(file_data: &[u8], entries: &[&CPIOEntry], chroot: &Chroot) -> usize {
+    let mut extracted_count = 0;
+
+    for entry in entries {
+        if extract_cpio_entry(file_data, entry, chroot) {
+            chroot.set_ownership(&entry.name, entry.uid, entry.gid);
+            chroot.set_mtime(&entry.name, entry.mtime);
+            extracted_count += 1;
+        }
+    }
+
+    extracted_count
+}
+
+/// Verifies the newc-CRC (`070702`) data checksum; non-CRC newc headers must carry a zero check
+/// field, and the odc/old binary formats carry no checksum at all
+fn verify_cpio_checksum(file_data: &[u8], data_offset: usize, header: &CPIOEntryHeader) -> bool {
+    match header.variant {
+        CPIOVariant::NewcCrc => match file_data.get(data_offset..data_offset + header.file_size) {
+            Some(data) => {
+                let computed_checksum = data.iter().fold(0u32, |sum, &b| sum.wrapping_add(b as u32));
+                computed_checksum == header.check
+            }
+            None => false,
+        },
+        CPIOVariant::Newc => header.check == 0,
+        CPIOVariant::Odc | CPIOVariant::BinaryLittleEndian | CPIOVariant::BinaryBigEndian => true,
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct CPIOManifestEntry {
+    path: String,
+    #[serde(rename = "type")]
+    file_type: &'static str,
+    mode: usize,
+    uid: usize,
+    gid: usize,
+    size: usize,
+    mtime: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rdev_major: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rdev_minor: Option<usize>,
+}
+
+/// Writes a JSON manifest of every parsed entry's metadata into the output directory, so
+/// downstream tooling can reason about the original filesystem without re-parsing the archive
+fn write_cpio_manifest(entries: &[CPIOEntry], chroot: &Chroot) {
+    let manifest: Vec<CPIOManifestEntry> = entries
+        .iter()
+        .map(|entry| {
+            let is_device = matches!(
+                entry.file_type,
+                CPIOFileType::BlockDevice | CPIOFileType::CharDevice
+            );
+
+            CPIOManifestEntry {
+                path: entry.name.clone(),
+                file_type: cpio_file_type_name(&entry.file_type),
+                mode: entry.mode,
+                uid: entry.uid,
+                gid: entry.gid,
+                size: entry.file_size,
+                mtime: entry.mtime,
+                rdev_major: is_device.then_some(entry.rdev_major),
+                rdev_minor: is_device.then_some(entry.rdev_minor),
+            }
+        })
+        .collect();
+
+    match serde_json::to_vec_pretty(&manifest) {
+        Ok(manifest_json) => {
+            if !chroot.append_to_file(MANIFEST_FILE_NAME, &manifest_json) {
+                warn!("Failed to write CPIO extraction manifest");
+            }
+        }
+        Err(e) => warn!("Failed to serialize CPIO extraction manifest: {}", e),
+    }
+}
+
+fn cpio_file_type_name(file_type: &CPIOFileType) -> &'static str {
+    match file_type {
+        CPIOFileType::Regular => "regular",
+        CPIOFileType::Directory => "directory",
+        CPIOFileType::Symlink => "symlink",
+        CPIOFileType::BlockDevice => "block_device",
+        CPIOFileType::CharDevice => "char_device",
+        CPIOFileType::Fifo => "fifo",
+        CPIOFileType::Socket => "socket",
+        CPIOFileType::Unknown => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A scratch output directory under the OS temp dir, unique per test and removed on drop
+    struct ScratchDir(PathBuf);
+
+    impl ScratchDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "binwalk-cpio-test-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).expect("failed to create scratch output directory");
+            ScratchDir(path)
+        }
+
+        fn path_str(&self) -> &str {
+            self.0.to_str().expect("scratch path must be valid UTF-8")
+        }
+    }
+
+    impl Drop for ScratchDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    /// Returns the number of padding bytes a newc-variant entry needs to round `n` up to a 4-byte
+    /// boundary
+    fn pad4(n: usize) -> usize {
+        let modulus = n % 4;
+        if modulus == 0 {
+            0
+        } else {
+            4 - modulus
+        }
+    }
+
+    /// Builds one raw "newc" (magic `070701`) entry: header, name, and padded data
+    #[allow(clippy::too_many_arguments)]
+    fn build_newc_entry(
+        ino: u32,
+        mode: u32,
+        uid: u32,
+        gid: u32,
+        nlink: u32,
+        mtime: u32,
+        name: &str,
+        data: &[u8],
+        rdev_major: u32,
+        rdev_minor: u32,
+    ) -> Vec<u8> {
+        let namesize = name.len() + 1;
+
+        let mut entry = String::new();
+        entry.push_str("070701");
+        entry.push_str(&format!("{:08x}", ino));
+        entry.push_str(&format!("{:08x}", mode));
+        entry.push_str(&format!("{:08x}", uid));
+        entry.push_str(&format!("{:08x}", gid));
+        entry.push_str(&format!("{:08x}", nlink));
+        entry.push_str(&format!("{:08x}", mtime));
+        entry.push_str(&format!("{:08x}", data.len()));
+        entry.push_str(&format!("{:08x}", 0)); // devmajor
+        entry.push_str(&format!("{:08x}", 0)); // devminor
+        entry.push_str(&format!("{:08x}", rdev_major));
+        entry.push_str(&format!("{:08x}", rdev_minor));
+        entry.push_str(&format!("{:08x}", namesize));
+        entry.push_str(&format!("{:08x}", 0)); // check
+
+        let mut bytes = entry.into_bytes();
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.push(0);
+        bytes.extend(std::iter::repeat(0u8).take(pad4(bytes.len())));
+        bytes.extend_from_slice(data);
+        bytes.extend(std::iter::repeat(0u8).take(pad4(data.len())));
+
+        bytes
+    }
+
+    /// Assembles a full newc archive out of already-built entries, appending the trailer record
+    fn build_newc_archive(entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut archive: Vec<u8> = entries.iter().flat_map(|e| e.iter().copied()).collect();
+        archive.extend(build_newc_entry(0, 0, 0, 0, 1, 0, EOF_MARKER, &[], 0, 0));
+        archive
+    }
+
+    #[test]
+    fn flags_entry_with_bad_newc_crc_checksum_as_corrupt() {
+        let data = b"hello world";
+        let mut entry = build_newc_entry(1, 0o100644, 0, 0, 1, 0, "bad.txt", data, 0, 0);
+        // Force the newc-CRC variant with a checksum that doesn't match the data
+        entry[0..6].copy_from_slice(b"070702");
+        let archive = build_newc_archive(&[entry]);
+
+        let result = extract_cpio(&archive, 0, None);
+
+        assert!(result.success);
+        assert_eq!(result.corrupt_entries, 1);
+    }
+
+    #[test]
+    fn reconstructs_hardlinked_entry_from_data_carrier() {
+        let scratch = ScratchDir::new("hardlink");
+        let data = b"shared file contents";
+
+        // First entry shares an inode with the second but carries no data of its own; the second
+        // entry is the data carrier. Both must end up with identical, correct file contents.
+        let placeholder = build_newc_entry(5, 0o100644, 0, 0, 2, 0, "link-a.txt", &[], 0, 0);
+        let carrier = build_newc_entry(5, 0o100644, 0, 0, 2, 0, "link-b.txt", data, 0, 0);
+        let archive = build_newc_archive(&[placeholder, carrier]);
+
+        let result = extract_cpio(&archive, 0, Some(scratch.path_str()));
+
+        assert!(result.success);
+        assert_eq!(
+            std::fs::read(scratch.0.join("link-a.txt")).expect("link-a.txt should be extracted"),
+            data
+        );
+        assert_eq!(
+            std::fs::read(scratch.0.join("link-b.txt")).expect("link-b.txt should be extracted"),
+            data
+        );
+    }
+
+    #[test]
+    fn writes_manifest_with_entry_metadata() {
+        let scratch = ScratchDir::new("manifest");
+        let entry = build_newc_entry(
+            3, 0o100755, 1000, 1000, 1, 1_700_000_000, "bin/app", b"ELF", 0, 0,
+        );
+        let archive = build_newc_archive(&[entry]);
+
+        let result = extract_cpio(&archive, 0, Some(scratch.path_str()));
+
+        assert!(result.success);
+        let manifest_bytes =
+            std::fs::read(scratch.0.join(MANIFEST_FILE_NAME)).expect("manifest should be written");
+        let manifest: serde_json::Value =
+            serde_json::from_slice(&manifest_bytes).expect("manifest should be valid JSON");
+
+        assert_eq!(manifest[0]["path"], "bin/app");
+        assert_eq!(manifest[0]["type"], "regular");
+        assert_eq!(manifest[0]["uid"], 1000);
+        assert_eq!(manifest[0]["gid"], 1000);
+        assert_eq!(manifest[0]["size"], 3);
+    }
+
+    #[test]
+    fn carves_every_plain_regular_entry() {
+        let scratch = ScratchDir::new("parallel-carve");
+        let entries = vec![
+            build_newc_entry(0, 0o100644, 0, 0, 1, 0, "one.txt", b"one", 0, 0),
+            build_newc_entry(0, 0o100644, 0, 0, 1, 0, "two.txt", b"two", 0, 0),
+            build_newc_entry(0, 0o100644, 0, 0, 1, 0, "three.txt", b"three", 0, 0),
+        ];
+        let archive = build_newc_archive(&entries);
+
+        let result = extract_cpio(&archive, 0, Some(scratch.path_str()));
+
+        assert!(result.success);
+        assert_eq!(std::fs::read(scratch.0.join("one.txt")).unwrap(), b"one");
+        assert_eq!(std::fs::read(scratch.0.join("two.txt")).unwrap(), b"two");
+        assert_eq!(std::fs::read(scratch.0.join("three.txt")).unwrap(), b"three");
     }
 }
@@ -0,0 +1,36 @@
+#![cfg(feature = "lzma")]
+
+use crate::extractors::common::{ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::stream::stream_decompress;
+use xz2::bufread::XzDecoder;
+use xz2::stream::Stream;
+
+/// Output file for decompressed data
+const OUTPUT_FILE_NAME: &str = "decompressed.bin";
+
+/// Defines the internal extractor function for decompressing raw LZMA ("alone" format) files
+pub fn lzma_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(lzma_decompressor),
+        ..Default::default()
+    }
+}
+
+/// Internal extractor for decompressing raw LZMA data, handling concatenated members
+pub fn lzma_decompressor(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    stream_decompress(
+        file_data,
+        offset,
+        output_directory,
+        OUTPUT_FILE_NAME,
+        |cursor| {
+            let stream = Stream::new_lzma_decoder(u64::MAX).ok()?;
+            Some(XzDecoder::new_stream(cursor, stream))
+        },
+        |decoder| decoder.into_inner().position() as usize,
+    )
+}
@@ -0,0 +1,197 @@
+use crate::extractors::common::{Chroot, ExtractionResult};
+use std::io::{Cursor, Read};
+
+/// Size of the streaming read buffer shared by every codec built on [`stream_decompress`]
+const STREAM_READ_BUF_SIZE: usize = 64 * 1024;
+
+/// Streams a concatenated-member compressed format to a single output file.
+///
+/// `make_decoder` builds a fresh decoder over the remaining input for each member, and
+/// `consumed_bytes` recovers how many input bytes that member's decoder actually read (typically
+/// by unwrapping back to the `Cursor` it was built from and reading its position). This is the
+/// bzip2 extractor's original member-by-member loop, generalized so xz, lzma and zstd can share
+/// one streaming core: read into a fixed buffer, track bytes consumed, stop on zero progress to
+/// avoid infinite loops, and append every member to the same output file.
+/// `make_decoder` returns `None` if the member's header is malformed in a way the decoder rejects
+/// at construction time (e.g. an invalid LZMA/zstd frame header); `stream_decompress` treats that
+/// the same as a member that consumed no input, stopping cleanly rather than propagating a panic
+/// up through attacker-controlled input.
+pub fn stream_decompress<'a, D, MakeDecoder, ConsumedBytes>(
+    file_data: &'a [u8],
+    offset: usize,
+    output_directory: Option<&str>,
+    output_file_name: &str,
+    make_decoder: MakeDecoder,
+    consumed_bytes: ConsumedBytes,
+) -> ExtractionResult
+where
+    D: Read,
+    MakeDecoder: Fn(Cursor<&'a [u8]>) -> Option<D>,
+    ConsumedBytes: Fn(D) -> usize,
+{
+    let mut result = ExtractionResult::default();
+
+    // Nothing to do if offset is past EOF
+    if offset >= file_data.len() {
+        return result;
+    }
+
+    let mut total_consumed: usize = 0;
+    let mut any_decompressed = false;
+    let mut current_offset = offset;
+
+    // Loop to handle concatenated members
+    loop {
+        if current_offset >= file_data.len() {
+            break;
+        }
+
+        let cursor = Cursor::new(&file_data[current_offset..]);
+        let Some(mut decoder) = make_decoder(cursor) else {
+            // Decoder initialization failed for this member -> stop, keeping prior members
+            break;
+        };
+        let mut read_buf = [0u8; STREAM_READ_BUF_SIZE];
+        let mut any_output_this_member = false;
+        // Many decoders, after exhausting the real member, make one more read() call to check
+        // for a following member and surface an `Err` when what follows isn't a valid header
+        // (e.g. ordinary trailing data after the last member). That must not discard the bytes
+        // this member already decoded, so every exit from this loop falls through to the
+        // consumed_bytes()/any_output_this_member bookkeeping below instead of returning early.
+        let mut member_errored = false;
+
+        loop {
+            match decoder.read(&mut read_buf) {
+                Ok(0) => {
+                    // EOF for this member (or no output right now)
+                    break;
+                }
+                Ok(n) => {
+                    // If extraction requested, append this decoded chunk
+                    if output_directory.is_some() {
+                        let chroot = Chroot::new(output_directory);
+                        if !chroot.append_to_file(output_file_name, &read_buf[..n]) {
+                            member_errored = true;
+                            break;
+                        }
+                    }
+
+                    any_output_this_member = true;
+                }
+                Err(_) => {
+                    member_errored = true;
+                    break;
+                }
+            }
+        }
+
+        if any_output_this_member {
+            any_decompressed = true;
+        }
+
+        let consumed = consumed_bytes(decoder);
+
+        if consumed > 0 {
+            current_offset += consumed;
+            total_consumed += consumed;
+        }
+
+        // Stop on a member error (nothing valid follows) or zero progress (avoid infinite loop)
+        if member_errored || consumed == 0 {
+            break;
+        }
+    }
+
+    result.success = any_decompressed;
+    result.size = if total_consumed > 0 { Some(total_consumed) } else { None };
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decodes a fixed payload on its first `read()`, then errors on the next call, mimicking a
+    /// real decoder that probes past the end of its member and trips over non-member trailing
+    /// bytes instead of cleanly reporting `Ok(0)`
+    struct ErrorsAfterOneMember {
+        payload: &'static [u8],
+        served: bool,
+        consumed: usize,
+    }
+
+    impl Read for ErrorsAfterOneMember {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.served {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "trailing data is not a valid member",
+                ));
+            }
+
+            self.served = true;
+            buf[..self.payload.len()].copy_from_slice(self.payload);
+            Ok(self.payload.len())
+        }
+    }
+
+    #[test]
+    fn preserves_member_output_when_trailing_data_errors() {
+        // A real compressed member followed by ordinary trailing bytes is the normal case for an
+        // embedded compressed blob that isn't the last thing in the scanned buffer, not a corner
+        // case; the member's own output must survive the probe-induced error on what follows.
+        let file_data = vec![0u8; 155];
+
+        let result = stream_decompress(
+            &file_data,
+            0,
+            None,
+            "decompressed.bin",
+            |_cursor| {
+                Some(ErrorsAfterOneMember {
+                    payload: b"hello, world",
+                    served: false,
+                    consumed: 100,
+                })
+            },
+            |decoder| decoder.consumed,
+        );
+
+        assert!(result.success);
+        assert_eq!(result.size, Some(100));
+    }
+
+    #[test]
+    fn does_not_report_success_when_output_write_fails() {
+        // Point the output "directory" at a plain file, so Chroot::append_to_file's
+        // create_dir_all can never succeed and every write fails; a member whose decoded output
+        // never made it to disk must not be reported as successfully extracted.
+        let blocker_path = std::env::temp_dir().join(format!(
+            "binwalk-stream-test-blocker-{}",
+            std::process::id()
+        ));
+        std::fs::write(&blocker_path, b"not a directory").expect("failed to create blocker file");
+
+        let file_data = vec![0u8; 16];
+
+        let result = stream_decompress(
+            &file_data,
+            0,
+            blocker_path.to_str(),
+            "decompressed.bin",
+            |_cursor| {
+                Some(ErrorsAfterOneMember {
+                    payload: b"hello",
+                    served: false,
+                    consumed: 16,
+                })
+            },
+            |decoder| decoder.consumed,
+        );
+
+        let _ = std::fs::remove_file(&blocker_path);
+
+        assert!(!result.success);
+    }
+}
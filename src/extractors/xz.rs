@@ -0,0 +1,32 @@
+#![cfg(feature = "xz")]
+
+use crate::extractors::common::{ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::stream::stream_decompress;
+use xz2::bufread::XzDecoder;
+
+/// Output file for decompressed data
+const OUTPUT_FILE_NAME: &str = "decompressed.bin";
+
+/// Defines the internal extractor function for decompressing XZ files
+pub fn xz_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(xz_decompressor),
+        ..Default::default()
+    }
+}
+
+/// Internal extractor for decompressing XZ data, handling concatenated members
+pub fn xz_decompressor(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    stream_decompress(
+        file_data,
+        offset,
+        output_directory,
+        OUTPUT_FILE_NAME,
+        |cursor| Some(XzDecoder::new(cursor)),
+        |decoder| decoder.into_inner().position() as usize,
+    )
+}
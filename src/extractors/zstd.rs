@@ -0,0 +1,36 @@
+#![cfg(feature = "zstd")]
+
+use crate::extractors::common::{ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::stream::stream_decompress;
+use zstd::stream::read::Decoder as ZstdDecoder;
+
+/// Output file for decompressed data
+const OUTPUT_FILE_NAME: &str = "decompressed.bin";
+
+/// Defines the internal extractor function for decompressing Zstandard files
+pub fn zstd_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(zstd_decompressor),
+        ..Default::default()
+    }
+}
+
+/// Internal extractor for decompressing Zstandard data, handling concatenated frames
+pub fn zstd_decompressor(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    stream_decompress(
+        file_data,
+        offset,
+        output_directory,
+        OUTPUT_FILE_NAME,
+        // `Cursor` is already a zero-copy `BufRead`, so decode straight over it via
+        // `with_buffer` instead of `Decoder::new`, which would wrap it in another `BufReader`;
+        // that extra buffering reads ahead of the actual frame boundary, so `.position()` on it
+        // overshoots the real consumed-bytes count instead of reporting it exactly
+        |cursor| ZstdDecoder::with_buffer(cursor).ok(),
+        |decoder| decoder.finish().position() as usize,
+    )
+}
@@ -1,19 +1,63 @@
 use crate::structures::common::StructureError;
 
-/// Expected minimum size of a CPIO entry header
+/// Expected minimum size of a "newc" (and checksummed "newc") CPIO entry header
 pub const CPIO_HEADER_SIZE: usize = 110;
 
+/// Expected minimum size of an old portable ASCII ("odc") CPIO entry header
+const CPIO_ODC_HEADER_SIZE: usize = 76;
+
+/// Expected minimum size of an old binary CPIO entry header
+const CPIO_BIN_HEADER_SIZE: usize = 26;
+
+/// Identifies which on-disk CPIO header layout a `CPIOEntryHeader` was parsed from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CPIOVariant {
+    /// "New" ASCII format, magic `070701`
+    Newc,
+    /// "New" ASCII format with a data checksum, magic `070702`
+    NewcCrc,
+    /// Old portable ASCII format, magic `070707`
+    Odc,
+    /// Old binary format, little-endian magic bytes `c7 71`
+    BinaryLittleEndian,
+    /// Old binary format, big-endian magic bytes `71 c7`
+    BinaryBigEndian,
+}
+
+impl Default for CPIOVariant {
+    fn default() -> Self {
+        CPIOVariant::Newc
+    }
+}
+
 /// Storage struct for CPIO entry header info
 #[derive(Debug, Clone, Default)]
 pub struct CPIOEntryHeader {
     pub magic: Vec<u8>,
+    pub variant: CPIOVariant,
+    /// Raw, unpadded size of this entry's file data
+    pub file_size: usize,
+    /// Size of this entry's file data once padded up to the next entry's header
     pub data_size: usize,
     pub file_name: String,
     pub header_size: usize,
     pub mode: usize,
     pub file_type: CPIOFileType,
+    pub uid: usize,
+    pub gid: usize,
+    pub mtime: u64,
+    /// Device the entry itself resides on; informational only, not used for node creation
     pub dev_major: usize,
     pub dev_minor: usize,
+    /// For device-special entries, the major/minor of the device node to create
+    pub rdev_major: usize,
+    pub rdev_minor: usize,
+    /// The header's check field, parsed for both newc variants: the data checksum for newc-CRC
+    /// (`070702`), zero in a valid plain newc (`070701`) entry; always `0` for odc and old binary
+    pub check: u32,
+    /// Inode number; entries sharing a non-zero `ino` with `nlink > 1` are hardlinks
+    pub ino: usize,
+    pub nlink: usize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -34,73 +78,142 @@ impl Default for CPIOFileType {
     }
 }
 
-/// Parses a CPIO entry header
+/// Parses a CPIO entry header, auto-detecting the newc, newc-CRC, odc, and old binary variants
 pub fn parse_cpio_entry_header(cpio_data: &[u8]) -> Result<CPIOEntryHeader, StructureError> {
+    match detect_cpio_variant(cpio_data) {
+        Some(variant @ (CPIOVariant::Newc | CPIOVariant::NewcCrc)) => {
+            parse_newc_header(cpio_data, variant)
+        }
+        Some(CPIOVariant::Odc) => parse_odc_header(cpio_data),
+        Some(variant @ (CPIOVariant::BinaryLittleEndian | CPIOVariant::BinaryBigEndian)) => {
+            parse_binary_header(cpio_data, variant)
+        }
+        None => Err(StructureError),
+    }
+}
+
+/// Identifies the CPIO variant from the leading magic bytes
+fn detect_cpio_variant(cpio_data: &[u8]) -> Option<CPIOVariant> {
+    if cpio_data.len() >= 6 {
+        match &cpio_data[0..6] {
+            b"070701" => return Some(CPIOVariant::Newc),
+            b"070702" => return Some(CPIOVariant::NewcCrc),
+            b"070707" => return Some(CPIOVariant::Odc),
+            _ => {}
+        }
+    }
+
+    match cpio_data.get(0..2) {
+        Some([0xc7, 0x71]) => Some(CPIOVariant::BinaryLittleEndian),
+        Some([0x71, 0xc7]) => Some(CPIOVariant::BinaryBigEndian),
+        _ => None,
+    }
+}
+
+/// Parses the "newc" and "newc"-with-checksum header layout (110-byte, hex ASCII fields)
+fn parse_newc_header(
+    cpio_data: &[u8],
+    variant: CPIOVariant,
+) -> Result<CPIOEntryHeader, StructureError> {
     const NULL_BYTE_SIZE: usize = 1;
     const CPIO_MAGIC_START: usize = 0;
     const CPIO_MAGIC_END: usize = 6;
+    const INO_START: usize = 6;
+    const INO_END: usize = 14;
     const MODE_START: usize = 14;
     const MODE_END: usize = 22;
-    const DEV_MAJOR_START: usize = 22;
-    const DEV_MAJOR_END: usize = 30;
-    const DEV_MINOR_START: usize = 30;
-    const DEV_MINOR_END: usize = 38;
+    const UID_START: usize = 22;
+    const UID_END: usize = 30;
+    const GID_START: usize = 30;
+    const GID_END: usize = 38;
+    const NLINK_START: usize = 38;
+    const NLINK_END: usize = 46;
+    const MTIME_START: usize = 46;
+    const MTIME_END: usize = 54;
     const FILE_SIZE_START: usize = 54;
     const FILE_SIZE_END: usize = 62;
+    const DEV_MAJOR_START: usize = 62;
+    const DEV_MAJOR_END: usize = 70;
+    const DEV_MINOR_START: usize = 70;
+    const DEV_MINOR_END: usize = 78;
+    const RDEV_MAJOR_START: usize = 78;
+    const RDEV_MAJOR_END: usize = 86;
+    const RDEV_MINOR_START: usize = 86;
+    const RDEV_MINOR_END: usize = 94;
     const FILE_NAME_SIZE_START: usize = 94;
     const FILE_NAME_SIZE_END: usize = 102;
+    const CHECK_START: usize = 102;
+    const CHECK_END: usize = 110;
 
     let available_data: usize = cpio_data.len();
 
     if available_data > CPIO_HEADER_SIZE {
         let header_magic = cpio_data[CPIO_MAGIC_START..CPIO_MAGIC_END].to_vec();
 
-        if let Ok(mode_str) = String::from_utf8(cpio_data[MODE_START..MODE_END].to_vec()) {
-            if let Ok(mode) = usize::from_str_radix(&mode_str, 16) {
-                if let Ok(dev_major_str) =
-                    String::from_utf8(cpio_data[DEV_MAJOR_START..DEV_MAJOR_END].to_vec())
+        if let Ok(mode) = parse_hex_field(cpio_data, MODE_START, MODE_END) {
+            if let Ok(file_data_size) = parse_hex_field(cpio_data, FILE_SIZE_START, FILE_SIZE_END) {
+                if let Ok(file_name_size) =
+                    parse_hex_field(cpio_data, FILE_NAME_SIZE_START, FILE_NAME_SIZE_END)
                 {
-                    if let Ok(dev_major) = usize::from_str_radix(&dev_major_str, 16) {
-                        if let Ok(dev_minor_str) =
-                            String::from_utf8(cpio_data[DEV_MINOR_START..DEV_MINOR_END].to_vec())
+                    let file_name_start: usize = CPIO_HEADER_SIZE;
+
+                    // A valid entry name is always NUL-terminated, so a zero namesize is invalid;
+                    // guard with checked arithmetic rather than letting the subtraction underflow
+                    if let Some(file_name_end) = file_name_size
+                        .checked_sub(NULL_BYTE_SIZE)
+                        .and_then(|n| file_name_start.checked_add(n))
+                    {
+                        if let Some(file_name_raw_bytes) =
+                            cpio_data.get(file_name_start..file_name_end)
                         {
-                            if let Ok(dev_minor) = usize::from_str_radix(&dev_minor_str, 16) {
-                                if let Ok(file_data_size_str) =
-                                    String::from_utf8(cpio_data[FILE_SIZE_START..FILE_SIZE_END].to_vec())
-                                {
-                                    if let Ok(file_data_size) = usize::from_str_radix(&file_data_size_str, 16) {
-                                        if let Ok(file_name_size_str) =
-                                            String::from_utf8(cpio_data[FILE_NAME_SIZE_START..FILE_NAME_SIZE_END].to_vec())
-                                        {
-                                            if let Ok(file_name_size) = usize::from_str_radix(&file_name_size_str, 16) {
-                                                let file_name_start: usize = CPIO_HEADER_SIZE;
-                                                let file_name_end: usize =
-                                                    file_name_start + file_name_size - NULL_BYTE_SIZE;
-
-                                                if let Some(file_name_raw_bytes) =
-                                                    cpio_data.get(file_name_start..file_name_end)
-                                                {
-                                                    if let Ok(file_name) = String::from_utf8(file_name_raw_bytes.to_vec()) {
-                                                        let header_total_size = CPIO_HEADER_SIZE + file_name_size;
-                                                        let file_type = parse_file_type(mode);
-
-                                                        return Ok(CPIOEntryHeader {
-                                                            magic: header_magic.clone(),
-                                                            file_name: file_name.clone(),
-                                                            data_size: file_data_size + byte_padding(file_data_size),
-                                                            header_size: header_total_size
-                                                                + byte_padding(header_total_size),
-                                                            mode,
-                                                            file_type,
-                                                            dev_major,
-                                                            dev_minor,
-                                                        });
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
+                            if let Ok(file_name) = String::from_utf8(file_name_raw_bytes.to_vec()) {
+                                let header_total_size = CPIO_HEADER_SIZE + file_name_size;
+                                let file_type = parse_file_type(mode);
+                                let uid = parse_hex_field(cpio_data, UID_START, UID_END).unwrap_or(0);
+                                let gid = parse_hex_field(cpio_data, GID_START, GID_END).unwrap_or(0);
+                                let ino = parse_hex_field(cpio_data, INO_START, INO_END).unwrap_or(0);
+                                let nlink =
+                                    parse_hex_field(cpio_data, NLINK_START, NLINK_END).unwrap_or(0);
+                                let mtime = parse_hex_field(cpio_data, MTIME_START, MTIME_END)
+                                    .unwrap_or(0) as u64;
+                                let dev_major =
+                                    parse_hex_field(cpio_data, DEV_MAJOR_START, DEV_MAJOR_END)
+                                        .unwrap_or(0);
+                                let dev_minor =
+                                    parse_hex_field(cpio_data, DEV_MINOR_START, DEV_MINOR_END)
+                                        .unwrap_or(0);
+                                let rdev_major =
+                                    parse_hex_field(cpio_data, RDEV_MAJOR_START, RDEV_MAJOR_END)
+                                        .unwrap_or(0);
+                                let rdev_minor =
+                                    parse_hex_field(cpio_data, RDEV_MINOR_START, RDEV_MINOR_END)
+                                        .unwrap_or(0);
+                                let check = parse_hex_field(cpio_data, CHECK_START, CHECK_END)
+                                    .map(|v| v as u32)
+                                    .unwrap_or(0);
+
+                                return Ok(CPIOEntryHeader {
+                                    magic: header_magic.clone(),
+                                    variant,
+                                    file_name: file_name.clone(),
+                                    file_size: file_data_size,
+                                    data_size: file_data_size
+                                        + byte_padding(variant, file_data_size),
+                                    header_size: header_total_size
+                                        + byte_padding(variant, header_total_size),
+                                    mode,
+                                    file_type,
+                                    uid,
+                                    gid,
+                                    mtime,
+                                    dev_major,
+                                    dev_minor,
+                                    rdev_major,
+                                    rdev_minor,
+                                    check,
+                                    ino,
+                                    nlink,
+                                });
                             }
                         }
                     }
@@ -112,9 +225,218 @@ pub fn parse_cpio_entry_header(cpio_data: &[u8]) -> Result<CPIOEntryHeader, Stru
     Err(StructureError)
 }
 
-fn byte_padding(n: usize) -> usize {
-    let modulus: usize = n % 4;
-    if modulus == 0 { 0 } else { 4 - modulus }
+/// Parses the old portable ASCII ("odc") header layout (76-byte, octal ASCII fields, no padding)
+fn parse_odc_header(cpio_data: &[u8]) -> Result<CPIOEntryHeader, StructureError> {
+    const NULL_BYTE_SIZE: usize = 1;
+    const MAGIC_START: usize = 0;
+    const MAGIC_END: usize = 6;
+    const DEV_START: usize = 6;
+    const DEV_END: usize = 12;
+    const INO_START: usize = 12;
+    const INO_END: usize = 18;
+    const MODE_START: usize = 18;
+    const MODE_END: usize = 24;
+    const UID_START: usize = 24;
+    const UID_END: usize = 30;
+    const GID_START: usize = 30;
+    const GID_END: usize = 36;
+    const NLINK_START: usize = 36;
+    const NLINK_END: usize = 42;
+    const RDEV_START: usize = 42;
+    const RDEV_END: usize = 48;
+    const MTIME_START: usize = 48;
+    const MTIME_END: usize = 59;
+    const FILE_NAME_SIZE_START: usize = 59;
+    const FILE_NAME_SIZE_END: usize = 65;
+    const FILE_SIZE_START: usize = 65;
+    const FILE_SIZE_END: usize = 76;
+
+    if cpio_data.len() <= CPIO_ODC_HEADER_SIZE {
+        return Err(StructureError);
+    }
+
+    let header_magic = cpio_data[MAGIC_START..MAGIC_END].to_vec();
+    let dev = parse_octal_field(cpio_data, DEV_START, DEV_END)?;
+    let ino = parse_octal_field(cpio_data, INO_START, INO_END)?;
+    let mode = parse_octal_field(cpio_data, MODE_START, MODE_END)?;
+    let uid = parse_octal_field(cpio_data, UID_START, UID_END)?;
+    let gid = parse_octal_field(cpio_data, GID_START, GID_END)?;
+    let nlink = parse_octal_field(cpio_data, NLINK_START, NLINK_END)?;
+    let rdev = parse_octal_field(cpio_data, RDEV_START, RDEV_END)?;
+    let mtime = parse_octal_field(cpio_data, MTIME_START, MTIME_END)? as u64;
+    let file_name_size = parse_octal_field(cpio_data, FILE_NAME_SIZE_START, FILE_NAME_SIZE_END)?;
+    let file_data_size = parse_octal_field(cpio_data, FILE_SIZE_START, FILE_SIZE_END)?;
+
+    let file_name_start = CPIO_ODC_HEADER_SIZE;
+    // A valid entry name is always NUL-terminated, so a zero namesize is invalid; guard with
+    // checked arithmetic rather than letting the subtraction underflow
+    let file_name_end = file_name_size
+        .checked_sub(NULL_BYTE_SIZE)
+        .and_then(|n| file_name_start.checked_add(n))
+        .ok_or(StructureError)?;
+
+    let file_name_raw_bytes = cpio_data
+        .get(file_name_start..file_name_end)
+        .ok_or(StructureError)?;
+    let file_name = String::from_utf8(file_name_raw_bytes.to_vec()).map_err(|_| StructureError)?;
+    let file_type = parse_file_type(mode);
+    let (dev_major, dev_minor) = split_dev(dev);
+    let (rdev_major, rdev_minor) = split_dev(rdev);
+
+    Ok(CPIOEntryHeader {
+        magic: header_magic,
+        variant: CPIOVariant::Odc,
+        file_name,
+        // odc pads neither the file name nor the file data
+        file_size: file_data_size,
+        data_size: file_data_size,
+        header_size: CPIO_ODC_HEADER_SIZE + file_name_size,
+        mode,
+        file_type,
+        uid,
+        gid,
+        mtime,
+        dev_major,
+        dev_minor,
+        rdev_major,
+        rdev_minor,
+        check: 0,
+        ino,
+        nlink,
+    })
+}
+
+/// Parses the old binary header layout (26-byte, 16-bit fields, 2-byte padding)
+fn parse_binary_header(
+    cpio_data: &[u8],
+    variant: CPIOVariant,
+) -> Result<CPIOEntryHeader, StructureError> {
+    const NULL_BYTE_SIZE: usize = 1;
+    const MAGIC_START: usize = 0;
+    const MAGIC_END: usize = 2;
+    const DEV_OFFSET: usize = 2;
+    const INO_OFFSET: usize = 4;
+    const MODE_OFFSET: usize = 6;
+    const UID_OFFSET: usize = 8;
+    const GID_OFFSET: usize = 10;
+    const NLINK_OFFSET: usize = 12;
+    const RDEV_OFFSET: usize = 14;
+    const MTIME_HI_OFFSET: usize = 16;
+    const MTIME_LO_OFFSET: usize = 18;
+    const FILE_NAME_SIZE_OFFSET: usize = 20;
+    const FILE_SIZE_HI_OFFSET: usize = 22;
+    const FILE_SIZE_LO_OFFSET: usize = 24;
+
+    if cpio_data.len() <= CPIO_BIN_HEADER_SIZE {
+        return Err(StructureError);
+    }
+
+    let big_endian = variant == CPIOVariant::BinaryBigEndian;
+    let header_magic = cpio_data[MAGIC_START..MAGIC_END].to_vec();
+
+    let dev = read_u16(cpio_data, DEV_OFFSET, big_endian)? as usize;
+    let ino = read_u16(cpio_data, INO_OFFSET, big_endian)? as usize;
+    let mode = read_u16(cpio_data, MODE_OFFSET, big_endian)? as usize;
+    let uid = read_u16(cpio_data, UID_OFFSET, big_endian)? as usize;
+    let gid = read_u16(cpio_data, GID_OFFSET, big_endian)? as usize;
+    let nlink = read_u16(cpio_data, NLINK_OFFSET, big_endian)? as usize;
+    let rdev = read_u16(cpio_data, RDEV_OFFSET, big_endian)? as usize;
+    let mtime = read_pdp11_u32(cpio_data, MTIME_HI_OFFSET, MTIME_LO_OFFSET, big_endian)? as u64;
+    let file_name_size = read_u16(cpio_data, FILE_NAME_SIZE_OFFSET, big_endian)? as usize;
+    let file_data_size =
+        read_pdp11_u32(cpio_data, FILE_SIZE_HI_OFFSET, FILE_SIZE_LO_OFFSET, big_endian)? as usize;
+
+    let file_name_start = CPIO_BIN_HEADER_SIZE;
+    // A valid entry name is always NUL-terminated, so a zero namesize is invalid; guard with
+    // checked arithmetic rather than letting the subtraction underflow
+    let file_name_end = file_name_size
+        .checked_sub(NULL_BYTE_SIZE)
+        .and_then(|n| file_name_start.checked_add(n))
+        .ok_or(StructureError)?;
+
+    let file_name_raw_bytes = cpio_data
+        .get(file_name_start..file_name_end)
+        .ok_or(StructureError)?;
+    let file_name = String::from_utf8(file_name_raw_bytes.to_vec()).map_err(|_| StructureError)?;
+    let file_type = parse_file_type(mode);
+    let header_total_size = CPIO_BIN_HEADER_SIZE + file_name_size;
+    let (dev_major, dev_minor) = split_dev(dev);
+    let (rdev_major, rdev_minor) = split_dev(rdev);
+
+    Ok(CPIOEntryHeader {
+        magic: header_magic,
+        variant,
+        file_name,
+        file_size: file_data_size,
+        data_size: file_data_size + byte_padding(variant, file_data_size),
+        header_size: header_total_size + byte_padding(variant, header_total_size),
+        mode,
+        file_type,
+        uid,
+        gid,
+        mtime,
+        dev_major,
+        dev_minor,
+        rdev_major,
+        rdev_minor,
+        check: 0,
+        ino,
+        nlink,
+    })
+}
+
+/// Splits a packed `dev_t`-style device number into (major, minor), as used by the odc and old
+/// binary formats, which store a single combined device field rather than separate major/minor
+fn split_dev(dev: usize) -> (usize, usize) {
+    ((dev >> 8) & 0xff, dev & 0xff)
+}
+
+/// Reads a 16-bit field at `offset`, honoring the old binary format's byte order
+fn read_u16(data: &[u8], offset: usize, big_endian: bool) -> Result<u16, StructureError> {
+    let field_bytes = data.get(offset..offset + 2).ok_or(StructureError)?;
+    Ok(if big_endian {
+        u16::from_be_bytes([field_bytes[0], field_bytes[1]])
+    } else {
+        u16::from_le_bytes([field_bytes[0], field_bytes[1]])
+    })
+}
+
+/// Reads a 32-bit field stored as two 16-bit halves in PDP-11 order (high half first)
+fn read_pdp11_u32(
+    data: &[u8],
+    hi_offset: usize,
+    lo_offset: usize,
+    big_endian: bool,
+) -> Result<u32, StructureError> {
+    let hi = read_u16(data, hi_offset, big_endian)? as u32;
+    let lo = read_u16(data, lo_offset, big_endian)? as u32;
+    Ok((hi << 16) | lo)
+}
+
+/// Parses an octal ASCII field, as used by the odc header
+fn parse_octal_field(data: &[u8], start: usize, end: usize) -> Result<usize, StructureError> {
+    let field_bytes = data.get(start..end).ok_or(StructureError)?;
+    let field_str = String::from_utf8(field_bytes.to_vec()).map_err(|_| StructureError)?;
+    usize::from_str_radix(&field_str, 8).map_err(|_| StructureError)
+}
+
+/// Parses a hex ASCII field, as used by the newc and newc-CRC headers
+fn parse_hex_field(data: &[u8], start: usize, end: usize) -> Result<usize, StructureError> {
+    let field_bytes = data.get(start..end).ok_or(StructureError)?;
+    let field_str = String::from_utf8(field_bytes.to_vec()).map_err(|_| StructureError)?;
+    usize::from_str_radix(&field_str, 16).map_err(|_| StructureError)
+}
+
+/// Returns the number of padding bytes needed to round `n` up to this variant's alignment
+fn byte_padding(variant: CPIOVariant, n: usize) -> usize {
+    let boundary = match variant {
+        CPIOVariant::Newc | CPIOVariant::NewcCrc => 4,
+        CPIOVariant::BinaryLittleEndian | CPIOVariant::BinaryBigEndian => 2,
+        CPIOVariant::Odc => return 0,
+    };
+
+    let modulus: usize = n % boundary;
+    if modulus == 0 { 0 } else { boundary - modulus }
 }
 
 fn parse_file_type(mode: usize) -> CPIOFileType {
@@ -145,3 +467,145 @@ pub fn is_executable(mode: usize) -> bool {
     const S_IXOTH: usize = 0o001;
     (mode & (S_IXUSR | S_IXGRP | S_IXOTH)) != 0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_odc_header(ino: u32, mode: u32, uid: u32, gid: u32, name: &str, file_data: &[u8]) -> Vec<u8> {
+        let namesize = name.len() + 1;
+
+        let mut header = String::new();
+        header.push_str("070707");
+        header.push_str(&format!("{:06o}", 0)); // dev
+        header.push_str(&format!("{:06o}", ino));
+        header.push_str(&format!("{:06o}", mode));
+        header.push_str(&format!("{:06o}", uid));
+        header.push_str(&format!("{:06o}", gid));
+        header.push_str(&format!("{:06o}", 1)); // nlink
+        header.push_str(&format!("{:06o}", 0)); // rdev
+        header.push_str(&format!("{:011o}", 0)); // mtime
+        header.push_str(&format!("{:06o}", namesize));
+        header.push_str(&format!("{:011o}", file_data.len()));
+
+        let mut data = header.into_bytes();
+        data.extend_from_slice(name.as_bytes());
+        data.push(0);
+        data.extend_from_slice(file_data);
+        data
+    }
+
+    fn build_binary_header(
+        big_endian: bool,
+        ino: u16,
+        mode: u16,
+        name: &str,
+        file_data: &[u8],
+    ) -> Vec<u8> {
+        let namesize = (name.len() + 1) as u16;
+        let file_size = file_data.len() as u32;
+
+        let mut data = if big_endian {
+            vec![0x71, 0xc7]
+        } else {
+            vec![0xc7, 0x71]
+        };
+        let push_u16 = |data: &mut Vec<u8>, v: u16| {
+            data.extend_from_slice(&if big_endian { v.to_be_bytes() } else { v.to_le_bytes() });
+        };
+        push_u16(&mut data, 0); // dev
+        push_u16(&mut data, ino);
+        push_u16(&mut data, mode);
+        push_u16(&mut data, 0); // uid
+        push_u16(&mut data, 0); // gid
+        push_u16(&mut data, 1); // nlink
+        push_u16(&mut data, 0); // rdev
+        push_u16(&mut data, 0); // mtime hi
+        push_u16(&mut data, 0); // mtime lo
+        push_u16(&mut data, namesize);
+        push_u16(&mut data, (file_size >> 16) as u16); // filesize hi
+        push_u16(&mut data, (file_size & 0xffff) as u16); // filesize lo
+
+        data.extend_from_slice(name.as_bytes());
+        data.push(0);
+        data.extend_from_slice(file_data);
+        data
+    }
+
+    #[test]
+    fn parses_odc_header() {
+        let data = build_odc_header(42, 0o100644, 1000, 1000, "hello.txt", b"abc");
+        let header = parse_cpio_entry_header(&data).expect("valid odc header should parse");
+
+        assert_eq!(header.variant, CPIOVariant::Odc);
+        assert_eq!(header.file_name, "hello.txt");
+        assert_eq!(header.ino, 42);
+        assert_eq!(header.uid, 1000);
+        assert_eq!(header.gid, 1000);
+        assert_eq!(header.file_size, 3);
+        assert_eq!(header.data_size, 3);
+        assert_eq!(header.header_size, CPIO_ODC_HEADER_SIZE + "hello.txt".len() + 1);
+        assert_eq!(header.file_type, CPIOFileType::Regular);
+    }
+
+    #[test]
+    fn parses_binary_header_little_endian() {
+        let data = build_binary_header(false, 7, 0o100644, "a.txt", b"hi");
+        let header =
+            parse_cpio_entry_header(&data).expect("valid little-endian binary header should parse");
+
+        assert_eq!(header.variant, CPIOVariant::BinaryLittleEndian);
+        assert_eq!(header.file_name, "a.txt");
+        assert_eq!(header.ino, 7);
+        assert_eq!(header.file_size, 2);
+        assert_eq!(header.file_type, CPIOFileType::Regular);
+    }
+
+    #[test]
+    fn parses_binary_header_big_endian() {
+        let data = build_binary_header(true, 9, 0o100644, "b.txt", b"hey");
+        let header =
+            parse_cpio_entry_header(&data).expect("valid big-endian binary header should parse");
+
+        assert_eq!(header.variant, CPIOVariant::BinaryBigEndian);
+        assert_eq!(header.file_name, "b.txt");
+        assert_eq!(header.ino, 9);
+        assert_eq!(header.file_size, 3);
+    }
+
+    #[test]
+    fn odc_header_rejects_zero_namesize() {
+        let mut header = String::new();
+        header.push_str("070707");
+        header.push_str(&"0".repeat(6 * 7)); // dev, ino, mode, uid, gid, nlink, rdev
+        header.push_str(&"0".repeat(11)); // mtime
+        header.push_str(&"0".repeat(6)); // namesize = 0
+        header.push_str(&"0".repeat(11)); // filesize
+
+        let mut data = header.into_bytes();
+        data.push(0); // pad past CPIO_ODC_HEADER_SIZE
+
+        assert!(parse_cpio_entry_header(&data).is_err());
+    }
+
+    #[test]
+    fn binary_header_rejects_zero_namesize() {
+        let mut data = vec![0xc7, 0x71];
+        data.extend(std::iter::repeat(0u8).take(2 * 10)); // dev..namesize, all zero
+        data.extend(std::iter::repeat(0u8).take(2 * 2)); // filesize hi/lo
+        data.push(0); // pad past CPIO_BIN_HEADER_SIZE
+
+        assert!(parse_cpio_entry_header(&data).is_err());
+    }
+
+    #[test]
+    fn newc_header_rejects_zero_namesize() {
+        let mut header = String::from("070701");
+        header.push_str(&"0".repeat(8 * 13)); // ino..check, all zero (namesize included)
+
+        let mut data = header.into_bytes();
+        data.push(0); // pad past CPIO_HEADER_SIZE
+
+        assert!(parse_cpio_entry_header(&data).is_err());
+    }
+}